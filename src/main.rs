@@ -1,8 +1,25 @@
 use nalgebra::Vector2;
+use std::collections::VecDeque;
 use std::time::{Duration, Instant};
 
+mod robot_trajectory;
+mod stp;
+
+// Radius around the enemy that `plan_path` and `bezier_path_blocked` treat as blocked
+const ENEMY_CLEARANCE_RADIUS: f64 = 1.0;
+// Steps of exponential approach the goalie is commanded along each time a new target is set
+const APPROACH_HORIZON: usize = 5;
+
 const INTERCEPT_THRESHOLD: f64 = 1.2; // Adjust to balance between interception and safe mode
-const ERROR_CORRECTION_FACTOR: f64 = 0.1; // Simple correction factor for trajectory prediction
+const ROLLOUT_DT: f64 = 0.02; // Fixed integration step used when simulating the ball forward
+
+const BALL_DRAG: f64 = 0.6; // Linear drag coefficient applied to the ball's velocity each step
+const BALL_STOP_SPEED: f64 = 0.05; // Below this speed the ball is considered to have stopped
+const WALL_RESTITUTION: f64 = 0.6; // Fraction of velocity retained after bouncing off a wall
+
+// Bounds of the known play area, reflected off of during the rollout
+const FIELD_HALF_WIDTH: f64 = 10.0;
+const FIELD_HALF_HEIGHT: f64 = 20.0;
 
 // Function for colored logging
 fn log(message: &str, color: &str) {
@@ -45,14 +62,48 @@ impl Ball {
         self.position = new_position;
     }
 
+    /// Simulate the ball forward `total_time` seconds in fixed `dt` steps, applying drag,
+    /// clamping to a stop once it rolls slow enough, and bouncing off the play area walls.
+    /// Returns the position sampled at the end of every step.
+    fn rollout(&self, total_time: f64, dt: f64) -> Vec<Vector2<f64>> {
+        let steps = (total_time / dt).ceil().max(1.0) as usize;
+        let mut position = self.position;
+        let mut velocity = self.velocity;
+        let mut samples = Vec::with_capacity(steps);
+
+        for _ in 0..steps {
+            velocity -= BALL_DRAG * velocity * dt;
+            if velocity.norm() < BALL_STOP_SPEED {
+                velocity = Vector2::new(0.0, 0.0);
+            }
+
+            position += velocity * dt;
+
+            if position.x.abs() > FIELD_HALF_WIDTH {
+                position.x = FIELD_HALF_WIDTH.copysign(position.x);
+                velocity.x = -velocity.x * WALL_RESTITUTION;
+            }
+            if position.y.abs() > FIELD_HALF_HEIGHT {
+                position.y = FIELD_HALF_HEIGHT.copysign(position.y);
+                velocity.y = -velocity.y * WALL_RESTITUTION;
+            }
+
+            samples.push(position);
+        }
+
+        samples
+    }
+
+    /// Predict where the ball will be `delta_time` seconds from now via `rollout`.
     fn predict_position(&self, delta_time: f64) -> Vector2<f64> {
-        let predicted_position = self.position
-            + self.velocity * delta_time
-            + 0.5 * self.acceleration * delta_time.powi(2)
-            + (1.0 / 6.0) * self.jerk * delta_time.powi(3);
+        if delta_time <= 0.0 {
+            return self.position;
+        }
 
-        // Apply simple error correction
-        predicted_position + ERROR_CORRECTION_FACTOR * (predicted_position - self.position)
+        *self
+            .rollout(delta_time, ROLLOUT_DT)
+            .last()
+            .expect("rollout always produces at least one sample")
     }
 }
 
@@ -73,25 +124,133 @@ impl Enemy {
     }
 }
 
+const GOALIE_MAX_VEL: f64 = 2.0; // Matches the previous fixed-speed assumption
+const GOALIE_MAX_ACCEL: f64 = 4.0;
+
+/// Tunable parameters for `Goalie::solve_rendezvous`'s coarse-scan-then-bisection search.
+struct RendezvousPhysics {
+    horizon: f64,
+    scan_step: f64,
+    bisection_iters: u32,
+}
+
+impl Default for RendezvousPhysics {
+    fn default() -> Self {
+        Self {
+            horizon: 5.0,
+            scan_step: 0.05,
+            bisection_iters: 20,
+        }
+    }
+}
+
 struct Goalie {
     position: Vector2<f64>,
+    velocity: Vector2<f64>,
+    max_vel: f64,
+    max_accel: f64,
+    target: Option<Vector2<f64>>,
+    // Remaining legs to drive through once `target` is reached
+    route: VecDeque<Vector2<f64>>,
+    // The role currently being executed; persists across ticks so a skill's own state (e.g.
+    // `Shoot::fired`) and `Role::current` carry over instead of resetting every decide_action.
+    role: Option<stp::Role>,
 }
 
 impl Goalie {
     fn new(position: Vector2<f64>) -> Self {
-        Self { position }
+        Self {
+            position,
+            velocity: Vector2::new(0.0, 0.0),
+            max_vel: GOALIE_MAX_VEL,
+            max_accel: GOALIE_MAX_ACCEL,
+            target: None,
+            route: VecDeque::new(),
+            role: None,
+        }
+    }
+
+    /// Queue up `waypoints` as a multi-leg route for `step()` to drive through in order.
+    fn drive_through(&mut self, waypoints: Vec<Vector2<f64>>) {
+        self.route = waypoints.into_iter().collect();
+        self.target = self.route.pop_front();
+    }
+
+    /// Duration of a trapezoidal velocity profile (accelerate, cruise, decelerate) covering
+    /// `distance`, collapsing to a triangular profile when `distance` is too short to reach
+    /// `max_vel`.
+    fn trapezoidal_duration(&self, distance: f64) -> f64 {
+        if distance <= 0.0 {
+            return 0.0;
+        }
+
+        let accel_distance = self.max_vel * self.max_vel / (2.0 * self.max_accel);
+
+        if distance >= 2.0 * accel_distance {
+            let accel_time = self.max_vel / self.max_accel;
+            let cruise_distance = distance - 2.0 * accel_distance;
+            let cruise_time = cruise_distance / self.max_vel;
+            2.0 * accel_time + cruise_time
+        } else {
+            2.0 * (distance / self.max_accel).sqrt()
+        }
     }
 
     fn time_to_reach(&self, target: &Vector2<f64>) -> f64 {
         let distance = (target - self.position).norm();
-        let speed = 2.0; // Assume a fixed speed for the goalie
-        distance / speed
+        self.trapezoidal_duration(distance)
+    }
+
+    /// Advance the goalie toward its current `target` by at most `max_accel`/`max_vel` per
+    /// tick, decelerating in time to stop exactly on arrival.
+    fn step(&mut self, dt: f64) {
+        let Some(target) = self.target else {
+            return;
+        };
+
+        let to_target = target - self.position;
+        let distance = to_target.norm();
+        if distance < 1e-6 {
+            self.position = target;
+            self.velocity = Vector2::new(0.0, 0.0);
+            // Arrived at this leg: continue on to the next one queued in the route, if any
+            self.target = self.route.pop_front();
+            return;
+        }
+
+        let direction = to_target / distance;
+        let current_speed = self.velocity.norm();
+        let braking_distance = current_speed * current_speed / (2.0 * self.max_accel);
+
+        let desired_speed = if distance <= braking_distance {
+            (current_speed - self.max_accel * dt).max(0.0)
+        } else {
+            (current_speed + self.max_accel * dt).min(self.max_vel)
+        };
+
+        self.velocity = direction * desired_speed;
+        let step_distance = (desired_speed * dt).min(distance);
+        self.position += direction * step_distance;
     }
 
-    fn bezier_path_blocked(&self, target: &Vector2<f64>, ball: &Ball, enemy: &Enemy) -> bool {
+    /// Generate a receding-horizon reference trajectory: a critically-damped exponential
+    /// approach from the current position to `target` over `horizon` steps. Per axis this is
+    /// `A * exp(B * h) + C`, with `C` the target coordinate, `A` the current error, and `B` a
+    /// decay rate tuned so the horizon covers most of the approach. Gives the motion
+    /// controller small, smoothly-shrinking steps instead of an instantaneous jump.
+    fn approach_trajectory(&self, target: Vector2<f64>, horizon: usize) -> Vec<Vector2<f64>> {
+        let decay_rate = -1.5 / horizon as f64;
+        let initial_error = self.position - target;
+
+        (1..=horizon)
+            .map(|h| target + initial_error * (decay_rate * h as f64).exp())
+            .collect()
+    }
+
+    fn bezier_path_blocked(&self, from: Vector2<f64>, target: Vector2<f64>, enemy: &Enemy) -> bool {
         // Simulate a simple bezier curve collision check
-        let mid_point = (self.position + target) * 0.5;
-        let path_points = vec![self.position, mid_point, *target];
+        let mid_point = (from + target) * 0.5;
+        let path_points = [from, mid_point, target];
 
         for t in (0..=10).map(|i| i as f64 / 10.0) {
             let bezier_point = (1.0 - t).powi(2) * path_points[0]
@@ -106,59 +265,190 @@ impl Goalie {
         false
     }
 
-    fn intercept(&mut self, ball: &Ball, target: Vector2<f64>, delta_time: f64) {
-        log(
-            &format!(
-                "[ACTION] Intercepting at position {:?}, moving towards {:?}",
-                ball.position, target
-            ),
-            "red",
-        );
+    /// Find the earliest time `t*` at which the goalie can be at the ball's predicted
+    /// position at that same instant, i.e. the root of `f(t) = time_to_reach(predict(t)) - t`.
+    /// Brackets the first sign change of `f` with a coarse scan, then refines it with
+    /// bisection. Returns `None` if `f` stays positive across the whole horizon, meaning the
+    /// ball is never catchable in that window.
+    fn solve_rendezvous(
+        &self,
+        ball: &Ball,
+        physics: &RendezvousPhysics,
+    ) -> Option<(f64, Vector2<f64>)> {
+        let f = |t: f64| self.time_to_reach(&ball.predict_position(t)) - t;
+
+        let mut prev_t = 0.0;
+        let mut prev_f = f(prev_t);
+        if prev_f == 0.0 {
+            return Some((prev_t, ball.predict_position(prev_t)));
+        }
 
-        let movement_vector = (target - self.position) / delta_time;
-        log(&format!("[INFO] Movement vector: {:?}", movement_vector), "blue");
+        let mut t = physics.scan_step;
+        while t <= physics.horizon {
+            let cur_f = f(t);
+
+            if cur_f.signum() != prev_f.signum() {
+                let (mut lo, mut f_lo) = (prev_t, prev_f);
+                let mut hi = t;
+
+                for _ in 0..physics.bisection_iters {
+                    let mid = 0.5 * (lo + hi);
+                    let f_mid = f(mid);
+                    if f_mid.signum() == f_lo.signum() {
+                        lo = mid;
+                        f_lo = f_mid;
+                    } else {
+                        hi = mid;
+                    }
+                }
+
+                let t_star = 0.5 * (lo + hi);
+                return Some((t_star, ball.predict_position(t_star)));
+            }
+
+            prev_t = t;
+            prev_f = cur_f;
+            t += physics.scan_step;
+        }
 
-        self.position = target; // Update position to the interception target
+        None
     }
 
     fn safe_mode(&mut self) {
         log("[INFO] Entering safe mode.", "green");
-        self.position = Vector2::new(0.0, 0.0); // Reset position
+        self.role = None;
+        self.drive_through(vec![Vector2::new(0.0, 0.0)]); // Drive back to the reset position
     }
 
+    /// Build the `Play` this tick should run: a "defend" play (`Intercept`, then `MoveTo`
+    /// legs around any obstacle, then `Shoot`) competing against a "hold" play that just
+    /// stays put. `Play::evaluate` scores both against `world` and the higher-scoring one is
+    /// picked; with any robot in `world` that is always "defend" (1.0 against 0.5).
+    fn build_play(
+        rendezvous_point: Vector2<f64>,
+        clear_target: Vector2<f64>,
+        path_blocked: bool,
+        obstacles: &[(Vector2<f64>, f64)],
+        hold_position: Vector2<f64>,
+    ) -> stp::Play {
+        let mut skills: Vec<Box<dyn stp::Skill>> = vec![Box::new(stp::Intercept::new())];
+
+        if path_blocked {
+            let waypoints = robot_trajectory::plan_path(rendezvous_point, clear_target, obstacles);
+            // Add a `MoveTo` for every leg of the route the visibility graph found, not just the
+            // first, so a multi-waypoint detour gets followed all the way to the target. Each
+            // leg's curve is optimized, but only its end point (the leg's end waypoint) becomes
+            // the target, so each leg is driven straight rather than along the curve.
+            for leg in waypoints.windows(2) {
+                let (leg_start, leg_end) = (leg[0], leg[1]);
+                let mut curve = robot_trajectory::RobotTrajectory::new(
+                    (leg_start.x, leg_start.y),
+                    (leg_end.x, leg_end.y),
+                    (leg_end.x, leg_end.y),
+                );
+                curve.optimize(obstacles, Duration::from_millis(20));
+                skills.push(Box::new(stp::MoveTo::new(curve.compute_position(1.0))));
+            }
+        }
+
+        skills.push(Box::new(stp::Shoot::new()));
+
+        let defend_play = stp::Play::new("defend", vec![stp::Role::new(skills)], |world| {
+            if world.robot_positions.is_empty() {
+                0.0
+            } else {
+                1.0
+            }
+        });
+        let hold_play = stp::Play::new(
+            "hold",
+            vec![stp::Role::new(vec![Box::new(stp::MoveTo::new(hold_position))])],
+            |world| if world.robot_positions.is_empty() { 0.0 } else { 0.5 },
+        );
+
+        let world = stp::WorldState {
+            ball_position: rendezvous_point,
+            robot_positions: vec![hold_position],
+            goal_position: clear_target,
+        };
+
+        if defend_play.evaluate(&world) >= hold_play.evaluate(&world) {
+            defend_play
+        } else {
+            hold_play
+        }
+    }
+
+    /// Decide what to do this tick and drive the goalie accordingly. The actual choice of
+    /// motion is delegated to a persisted `stp::Role`: it is only rebuilt once the previous
+    /// one completes (or there is none yet), so skill state like `Shoot::fired` and
+    /// `Role::current` survives across ticks instead of resetting every call. The role's
+    /// chosen target is then expanded into a smooth exponential approach via
+    /// `approach_trajectory` for `step()` to drive.
     fn decide_action(
         &mut self,
         ball: &Ball,
         enemy: &Enemy,
         teammate_position: Vector2<f64>,
         goal_position: Vector2<f64>,
-        delta_time: f64,
+        physics: &RendezvousPhysics,
     ) {
-        let predicted_ball_position = ball.predict_position(delta_time);
+        let Some((t_star, rendezvous_point)) = self.solve_rendezvous(ball, physics) else {
+            log("[INFO] Ball is never catchable within the horizon. Entering safe mode.", "green");
+            self.safe_mode();
+            return;
+        };
+
         log(
             &format!(
-                "[DEBUG] Predicted ball position in {}s: {:?}",
-                delta_time, predicted_ball_position
+                "[DEBUG] Rendezvous at t={:.2}s, position {:?}",
+                t_star, rendezvous_point
             ),
             "blue",
         );
 
-        let goalie_time = self.time_to_reach(&predicted_ball_position);
-        let enemy_time = enemy.time_to_reach(&predicted_ball_position);
+        let enemy_time = enemy.time_to_reach(&rendezvous_point);
+        if t_star > enemy_time * INTERCEPT_THRESHOLD {
+            log("[INFO] Enemy might reach the ball first. Entering safe mode.", "green");
+            self.safe_mode();
+            return;
+        }
 
-        if goalie_time <= enemy_time * INTERCEPT_THRESHOLD {
-            let target = if self.bezier_path_blocked(&goal_position, ball, enemy) {
-                log("[INFO] Path to goal is blocked. Passing to teammate.", "yellow");
-                teammate_position
-            } else {
-                log("[INFO] Path to goal is clear. Shooting towards goal.", "yellow");
-                goal_position
-            };
+        let obstacles = [(enemy.position, ENEMY_CLEARANCE_RADIUS)];
+        let path_blocked = self.bezier_path_blocked(rendezvous_point, goal_position, enemy);
 
-            self.intercept(ball, target, delta_time);
+        let clear_target = if path_blocked {
+            log("[INFO] Path to goal is blocked. Passing to teammate.", "yellow");
+            teammate_position
         } else {
-            log("[INFO] Enemy might reach the ball first. Entering safe mode.", "green");
-            self.safe_mode();
+            log("[INFO] Path to goal is clear. Shooting towards goal.", "yellow");
+            goal_position
+        };
+
+        if self.role.as_ref().is_none_or(stp::Role::is_complete) {
+            let play = Self::build_play(rendezvous_point, clear_target, path_blocked, &obstacles, self.position);
+            log(&format!("[DEBUG] Selected play '{}'", play.name), "blue");
+            self.role = play.roles.into_iter().next();
+        }
+
+        let world = stp::WorldState {
+            ball_position: rendezvous_point,
+            robot_positions: vec![self.position],
+            goal_position: clear_target,
+        };
+
+        let Some(role) = self.role.as_mut() else {
+            return;
+        };
+        let command = role.command(0, &world);
+        let role_complete = role.is_complete();
+
+        if let Some(command) = command {
+            let trajectory = self.approach_trajectory(command.target, APPROACH_HORIZON);
+            self.drive_through(trajectory);
+        }
+        if role_complete {
+            log("[DEBUG] Role completed its skill sequence.", "blue");
         }
     }
 
@@ -173,6 +463,18 @@ fn main() {
     let enemy = Enemy::new(Vector2::new(1.0, 5.0), Vector2::new(0.2, -0.1));
     let teammate_position = Vector2::new(-5.0, 10.0);
     let goal_position = Vector2::new(0.0, -20.0);
+    let rendezvous_physics = RendezvousPhysics::default();
+
+    // Only one robot and one defensive role on the field today, but go through the dealer
+    // anyway so adding a second robot later is a matter of growing these two lists.
+    let robots = [goalie.position];
+    let assignment = stp::Dealer::assign(1, robots.len(), |_role, robot| {
+        -(robots[robot] - ball.position).norm()
+    });
+    log(
+        &format!("[INFO] Dealer assigned goalie role to robot slot {:?}", assignment[0]),
+        "blue",
+    );
 
     let mut last_update = Instant::now();
 
@@ -185,12 +487,99 @@ fn main() {
         ball.update(ball.position + Vector2::new(0.1, -0.2), delta_time);
 
         if Goalie::in_target_box(&ball) {
-            goalie.decide_action(&ball, &enemy, teammate_position, goal_position, delta_time);
+            goalie.decide_action(&ball, &enemy, teammate_position, goal_position, &rendezvous_physics);
         } else {
             log("[INFO] Ball is outside the target box. Maintaining position.", "default");
         }
 
+        goalie.step(delta_time);
+
         std::thread::sleep(Duration::from_millis(100));
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stationary_ball(position: Vector2<f64>) -> Ball {
+        Ball {
+            position,
+            velocity: Vector2::new(0.0, 0.0),
+            acceleration: Vector2::new(0.0, 0.0),
+            jerk: Vector2::new(0.0, 0.0),
+        }
+    }
+
+    #[test]
+    fn rollout_bounces_off_wall_with_restitution() {
+        let ball = Ball {
+            position: Vector2::new(9.0, 0.0),
+            velocity: Vector2::new(5.0, 0.0),
+            acceleration: Vector2::new(0.0, 0.0),
+            jerk: Vector2::new(0.0, 0.0),
+        };
+
+        let samples = ball.rollout(1.0, ROLLOUT_DT);
+
+        assert!(
+            samples.iter().any(|p| (p.x - FIELD_HALF_WIDTH).abs() < 1e-6),
+            "ball should reach the wall at x = {FIELD_HALF_WIDTH}"
+        );
+        assert!(
+            samples.iter().all(|p| p.x <= FIELD_HALF_WIDTH + 1e-9),
+            "no sample should cross past the wall"
+        );
+
+        // Right after the bounce the ball moves back at `WALL_RESTITUTION` of its incoming
+        // speed, less the drag of the two steps in between.
+        let k = samples
+            .iter()
+            .position(|p| (p.x - FIELD_HALF_WIDTH).abs() < 1e-6)
+            .unwrap();
+        let incoming = samples[k - 1].x - samples[k - 2].x;
+        let outgoing = samples[k + 1].x - samples[k].x;
+        let expected_ratio = -WALL_RESTITUTION * (1.0 - BALL_DRAG * ROLLOUT_DT).powi(2);
+        assert!(
+            (outgoing / incoming - expected_ratio).abs() < 1e-9,
+            "ball should bounce back with restitution {WALL_RESTITUTION}"
+        );
+    }
+
+    #[test]
+    fn step_reaches_target_without_overshoot() {
+        let mut goalie = Goalie::new(Vector2::new(0.0, 0.0));
+        let target = Vector2::new(1.0, 0.0);
+        goalie.target = Some(target);
+
+        for _ in 0..1000 {
+            goalie.step(0.01);
+            if goalie.target.is_none() {
+                break;
+            }
+        }
+
+        assert!(goalie.target.is_none(), "goalie should have arrived at the target");
+        assert!((goalie.position - target).norm() < 1e-6);
+        assert!(goalie.position.x <= target.x + 1e-6, "goalie should not overshoot the target");
+    }
+
+    #[test]
+    fn solve_rendezvous_finds_a_catchable_ball() {
+        let physics = RendezvousPhysics::default();
+        let goalie = Goalie::new(Vector2::new(0.0, 0.0));
+        let ball = stationary_ball(Vector2::new(1.0, 0.0));
+
+        assert!(goalie.solve_rendezvous(&ball, &physics).is_some());
+    }
+
+    #[test]
+    fn solve_rendezvous_returns_none_when_unreachable_within_the_horizon() {
+        let physics = RendezvousPhysics::default();
+        let goalie = Goalie::new(Vector2::new(0.0, 0.0));
+        let ball = stationary_ball(Vector2::new(9.9, 19.9));
+
+        assert!(goalie.solve_rendezvous(&ball, &physics).is_none());
+    }
+}
+
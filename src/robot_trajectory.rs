@@ -1,4 +1,6 @@
 use nalgebra::Vector2;
+use rand::Rng;
+use std::time::{Duration, Instant};
 
 /// Represents a robot's trajectory using a Cubic Bézier curve
 #[derive(Debug, Clone)]
@@ -10,6 +12,7 @@ pub struct RobotTrajectory {
 }
 
 /// Contains key trajectory information for quick access
+#[allow(dead_code)] // only exercised by tests so far; kept for debugging/tuning sessions
 #[derive(Debug, Clone)]
 pub struct TrajectoryInfo {
     pub start_pos: (f64, f64),
@@ -66,11 +69,13 @@ impl RobotTrajectory {
     }
 
     /// Compute movement vector for given delta t
+    #[allow(dead_code)] // only exercised by tests so far; kept for debugging/tuning sessions
     pub fn compute_delta_vector(&self, delta_t: f64) -> Vector2<f64> {
         self.compute_position(delta_t) - self.compute_position(0.0)
     }
 
     /// Utility function to get trajectory information
+    #[allow(dead_code)] // only exercised by tests so far; kept for debugging/tuning sessions
     pub fn get_trajectory_info(&self) -> TrajectoryInfo {
         TrajectoryInfo {
             start_pos: (self.start_pos.x, self.start_pos.y),
@@ -85,7 +90,109 @@ impl RobotTrajectory {
         }
     }
 
+    /// Number of points sampled along the curve when scoring a candidate during `optimize`.
+    const OPTIMIZE_SAMPLES: usize = 20;
+
+    /// Cost of a candidate curve: a large penalty per sampled point that falls inside an
+    /// obstacle, plus a weighted sum of total arc length and curvature (total turning angle).
+    fn curve_cost(control_points: &[Vector2<f64>; 4], obstacles: &[(Vector2<f64>, f64)]) -> f64 {
+        const OBSTACLE_PENALTY: f64 = 1000.0;
+        const LENGTH_WEIGHT: f64 = 1.0;
+        const CURVATURE_WEIGHT: f64 = 5.0;
+
+        let samples: Vec<Vector2<f64>> = (0..=Self::OPTIMIZE_SAMPLES)
+            .map(|i| {
+                let t = i as f64 / Self::OPTIMIZE_SAMPLES as f64;
+                let one_minus_t = 1.0 - t;
+                one_minus_t.powi(3) * control_points[0]
+                    + 3.0 * one_minus_t.powi(2) * t * control_points[1]
+                    + 3.0 * one_minus_t * t.powi(2) * control_points[2]
+                    + t.powi(3) * control_points[3]
+            })
+            .collect();
+
+        let obstacle_penalty: f64 = samples
+            .iter()
+            .map(|point| {
+                obstacles
+                    .iter()
+                    .map(|(center, radius)| {
+                        let distance = (point - center).norm();
+                        if distance < *radius {
+                            OBSTACLE_PENALTY * (radius - distance)
+                        } else {
+                            0.0
+                        }
+                    })
+                    .sum::<f64>()
+            })
+            .sum();
+
+        let arc_length: f64 = samples
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).norm())
+            .sum();
+
+        let curvature: f64 = samples
+            .windows(3)
+            .map(|triple| (triple[1] - triple[0]).angle(&(triple[2] - triple[1])).abs())
+            .sum();
+
+        obstacle_penalty + LENGTH_WEIGHT * arc_length + CURVATURE_WEIGHT * curvature
+    }
+
+    /// Optimize the two interior control points via simulated annealing so the curve avoids
+    /// `obstacles` (modeled as circles) while staying short and smooth. Runs until
+    /// `time_budget` elapses; the start and end points are never moved.
+    pub fn optimize(&mut self, obstacles: &[(Vector2<f64>, f64)], time_budget: Duration) {
+        const INITIAL_TEMPERATURE: f64 = 10.0;
+        const COOLING_RATE: f64 = 0.98;
+        const STEP_SCALE: f64 = 2.0;
+
+        let mut rng = rand::thread_rng();
+        let deadline = Instant::now() + time_budget;
+
+        let mut current = self.control_points;
+        let mut current_cost = Self::curve_cost(&current, obstacles);
+        let mut best = current;
+        let mut best_cost = current_cost;
+        let mut temperature = INITIAL_TEMPERATURE;
+
+        while Instant::now() < deadline {
+            let mut candidate = current;
+            // Gaussian step via Box-Muller, scaled by the current temperature so early,
+            // hot steps roam widely and later, cooled steps settle into small adjustments.
+            let gaussian_step = |rng: &mut rand::rngs::ThreadRng| {
+                let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+                let u2: f64 = rng.gen_range(0.0..1.0);
+                let radius = (-2.0 * u1.ln()).sqrt();
+                let theta = 2.0 * std::f64::consts::PI * u2;
+                Vector2::new(radius * theta.cos(), radius * theta.sin()) * STEP_SCALE * temperature
+            };
+            candidate[1] += gaussian_step(&mut rng);
+            candidate[2] += gaussian_step(&mut rng);
+
+            let candidate_cost = Self::curve_cost(&candidate, obstacles);
+            let delta = candidate_cost - current_cost;
+
+            if delta <= 0.0 || rng.gen::<f64>() < (-delta / temperature).exp() {
+                current = candidate;
+                current_cost = candidate_cost;
+
+                if current_cost < best_cost {
+                    best = current;
+                    best_cost = current_cost;
+                }
+            }
+
+            temperature *= COOLING_RATE;
+        }
+
+        self.control_points = best;
+    }
+
     // Debug/development methods (optional)
+    #[allow(dead_code)] // only exercised by tests so far; kept for debugging/tuning sessions
     pub fn print_path_details(&self) {
         println!("Trajectory Details:");
         println!("Start Position: ({}, {})", self.start_pos.x, self.start_pos.y);
@@ -100,6 +207,132 @@ impl RobotTrajectory {
     }
 }
 
+const VISIBILITY_WAYPOINTS_PER_OBSTACLE: usize = 8;
+const VISIBILITY_MARGIN: f64 = 0.2; // clearance added beyond each obstacle's radius
+
+/// Plan the shortest collision-free path from `start` to `goal` around circular
+/// `obstacles` using a visibility graph: candidate waypoints ring each obstacle, an edge
+/// joins any two nodes whose straight segment clears every obstacle, and Dijkstra finds the
+/// shortest polyline through that graph. Returns an empty path if `goal` is unreachable.
+pub fn plan_path(
+    start: Vector2<f64>,
+    goal: Vector2<f64>,
+    obstacles: &[(Vector2<f64>, f64)],
+) -> Vec<Vector2<f64>> {
+    let mut nodes = vec![start, goal];
+    for (center, radius) in obstacles {
+        let offset_radius = radius + VISIBILITY_MARGIN;
+        for i in 0..VISIBILITY_WAYPOINTS_PER_OBSTACLE {
+            let angle =
+                2.0 * std::f64::consts::PI * i as f64 / VISIBILITY_WAYPOINTS_PER_OBSTACLE as f64;
+            nodes.push(center + Vector2::new(angle.cos(), angle.sin()) * offset_radius);
+        }
+    }
+
+    let segment_clears_obstacles = |a: Vector2<f64>, b: Vector2<f64>| {
+        obstacles
+            .iter()
+            .all(|(center, radius)| segment_point_distance(a, b, *center) >= *radius)
+    };
+
+    let node_count = nodes.len();
+    let mut edges: Vec<Vec<(usize, f64)>> = vec![Vec::new(); node_count];
+    for i in 0..node_count {
+        for j in (i + 1)..node_count {
+            if segment_clears_obstacles(nodes[i], nodes[j]) {
+                let weight = (nodes[j] - nodes[i]).norm();
+                edges[i].push((j, weight));
+                edges[j].push((i, weight));
+            }
+        }
+    }
+
+    dijkstra_path(&edges, 0, 1)
+        .into_iter()
+        .map(|index| nodes[index])
+        .collect()
+}
+
+/// Shortest distance from point `p` to the segment `a`-`b`.
+fn segment_point_distance(a: Vector2<f64>, b: Vector2<f64>, p: Vector2<f64>) -> f64 {
+    let ab = b - a;
+    let len_sq = ab.norm_squared();
+    if len_sq < 1e-12 {
+        return (p - a).norm();
+    }
+
+    let t = ((p - a).dot(&ab) / len_sq).clamp(0.0, 1.0);
+    let projection = a + ab * t;
+    (p - projection).norm()
+}
+
+/// Dijkstra shortest path over an adjacency list, returning node indices from `start` to
+/// `goal`, or an empty path if `goal` is unreachable.
+fn dijkstra_path(edges: &[Vec<(usize, f64)>], start: usize, goal: usize) -> Vec<usize> {
+    use std::cmp::Ordering;
+    use std::collections::BinaryHeap;
+
+    struct State {
+        cost: f64,
+        node: usize,
+    }
+
+    impl PartialEq for State {
+        fn eq(&self, other: &Self) -> bool {
+            self.cost == other.cost
+        }
+    }
+    impl Eq for State {}
+    impl Ord for State {
+        fn cmp(&self, other: &Self) -> Ordering {
+            // Reversed so `BinaryHeap` (a max-heap) pops the smallest cost first
+            other.cost.partial_cmp(&self.cost).unwrap_or(Ordering::Equal)
+        }
+    }
+    impl PartialOrd for State {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    let mut dist = vec![f64::INFINITY; edges.len()];
+    let mut prev = vec![None; edges.len()];
+    dist[start] = 0.0;
+
+    let mut heap = BinaryHeap::new();
+    heap.push(State { cost: 0.0, node: start });
+
+    while let Some(State { cost, node }) = heap.pop() {
+        if node == goal {
+            break;
+        }
+        if cost > dist[node] {
+            continue;
+        }
+        for &(next, weight) in &edges[node] {
+            let next_cost = cost + weight;
+            if next_cost < dist[next] {
+                dist[next] = next_cost;
+                prev[next] = Some(node);
+                heap.push(State { cost: next_cost, node: next });
+            }
+        }
+    }
+
+    if dist[goal].is_infinite() {
+        return Vec::new();
+    }
+
+    let mut path = vec![goal];
+    let mut current = goal;
+    while let Some(p) = prev[current] {
+        path.push(p);
+        current = p;
+    }
+    path.reverse();
+    path
+}
+
 // Example usage
 #[cfg(test)]
 mod tests {
@@ -133,4 +366,46 @@ mod tests {
         let info = trajectory.get_trajectory_info();
         println!("\nTrajectory Info: {:?}", info);
     }
+
+    #[test]
+    fn optimize_reduces_cost_and_keeps_endpoints_fixed() {
+        let mut trajectory = RobotTrajectory::new((0.0, 0.0), (10.0, 0.0), (15.0, 8.0));
+        let obstacles = [(Vector2::new(5.0, 0.0), 2.0)]; // sits right on the straight-line path
+
+        let original_control_points = trajectory.control_points;
+        let cost_before = RobotTrajectory::curve_cost(&trajectory.control_points, &obstacles);
+
+        trajectory.optimize(&obstacles, Duration::from_millis(200));
+
+        let cost_after = RobotTrajectory::curve_cost(&trajectory.control_points, &obstacles);
+
+        assert!(cost_after < cost_before);
+        assert_eq!(trajectory.control_points[0], original_control_points[0]);
+        assert_eq!(trajectory.control_points[3], original_control_points[3]);
+    }
+
+    #[test]
+    fn plan_path_routes_around_a_single_obstacle() {
+        let start = Vector2::new(0.0, 0.0);
+        let goal = Vector2::new(10.0, 0.0);
+        let obstacles = [(Vector2::new(5.0, 0.0), 1.0)];
+
+        let path = plan_path(start, goal, &obstacles);
+
+        assert!(path.len() >= 2);
+        assert_eq!(*path.first().unwrap(), start);
+        assert_eq!(*path.last().unwrap(), goal);
+        for window in path.windows(2) {
+            assert!(segment_point_distance(window[0], window[1], obstacles[0].0) >= obstacles[0].1);
+        }
+    }
+
+    #[test]
+    fn plan_path_is_empty_when_goal_is_fully_enclosed() {
+        let start = Vector2::new(0.0, 0.0);
+        let goal = Vector2::new(10.0, 0.0);
+        let obstacles = [(goal, 1000.0)];
+
+        assert!(plan_path(start, goal, &obstacles).is_empty());
+    }
 }
@@ -0,0 +1,252 @@
+use nalgebra::Vector2;
+
+/// Per-tick snapshot of everything a `Skill` needs to compute a motion command.
+#[derive(Debug, Clone)]
+pub struct WorldState {
+    pub ball_position: Vector2<f64>,
+    pub robot_positions: Vec<Vector2<f64>>,
+    pub goal_position: Vector2<f64>,
+}
+
+/// A motion command produced by a `Skill`: where the robot should be driven next.
+#[derive(Debug, Clone, Copy)]
+pub struct MotionCommand {
+    pub target: Vector2<f64>,
+}
+
+/// An atomic action a single robot can execute given the current `WorldState`.
+pub trait Skill {
+    /// Compute the next motion command for the robot at `robot_index`.
+    fn command(&mut self, robot_index: usize, world: &WorldState) -> MotionCommand;
+
+    /// Whether this skill has achieved its goal and the role can advance to the next one.
+    fn is_done(&self, robot_index: usize, world: &WorldState) -> bool;
+}
+
+/// Drive straight towards a fixed point.
+pub struct MoveTo {
+    pub target: Vector2<f64>,
+    arrival_radius: f64,
+}
+
+impl MoveTo {
+    pub fn new(target: Vector2<f64>) -> Self {
+        Self {
+            target,
+            arrival_radius: 0.1,
+        }
+    }
+}
+
+impl Skill for MoveTo {
+    fn command(&mut self, _robot_index: usize, _world: &WorldState) -> MotionCommand {
+        MotionCommand {
+            target: self.target,
+        }
+    }
+
+    fn is_done(&self, robot_index: usize, world: &WorldState) -> bool {
+        (world.robot_positions[robot_index] - self.target).norm() <= self.arrival_radius
+    }
+}
+
+/// Drive to meet the ball.
+pub struct Intercept {
+    arrival_radius: f64,
+}
+
+impl Intercept {
+    pub fn new() -> Self {
+        Self { arrival_radius: 0.3 }
+    }
+}
+
+impl Default for Intercept {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Skill for Intercept {
+    fn command(&mut self, _robot_index: usize, world: &WorldState) -> MotionCommand {
+        MotionCommand {
+            target: world.ball_position,
+        }
+    }
+
+    fn is_done(&self, robot_index: usize, world: &WorldState) -> bool {
+        (world.robot_positions[robot_index] - world.ball_position).norm() <= self.arrival_radius
+    }
+}
+
+/// Aim a single command at the goal. A shot is a single instantaneous command, not a
+/// sustained approach, so it reports done right after that one command is issued.
+pub struct Shoot {
+    fired: bool,
+}
+
+impl Shoot {
+    pub fn new() -> Self {
+        Self { fired: false }
+    }
+}
+
+impl Default for Shoot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Skill for Shoot {
+    fn command(&mut self, _robot_index: usize, world: &WorldState) -> MotionCommand {
+        self.fired = true;
+        MotionCommand {
+            target: world.goal_position,
+        }
+    }
+
+    fn is_done(&self, _robot_index: usize, _world: &WorldState) -> bool {
+        self.fired
+    }
+}
+
+/// Sequences `Skill`s for a single robot, advancing once the active skill reports done.
+pub struct Role {
+    skills: Vec<Box<dyn Skill>>,
+    current: usize,
+}
+
+impl Role {
+    pub fn new(skills: Vec<Box<dyn Skill>>) -> Self {
+        Self { skills, current: 0 }
+    }
+
+    /// Compute this role's motion command for `robot_index`, advancing to the next skill in
+    /// the sequence once the active one reports done. Returns `None` once every skill in the
+    /// sequence is complete.
+    pub fn command(&mut self, robot_index: usize, world: &WorldState) -> Option<MotionCommand> {
+        while self.current < self.skills.len()
+            && self.skills[self.current].is_done(robot_index, world)
+        {
+            self.current += 1;
+        }
+
+        self.skills
+            .get_mut(self.current)
+            .map(|skill| skill.command(robot_index, world))
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.current >= self.skills.len()
+    }
+}
+
+/// A coordinated multi-robot strategy: one `Role` per robot slot, plus a scoring function
+/// for how well this play fits the current `WorldState` so the best play can be selected
+/// each tick.
+pub struct Play {
+    pub name: &'static str,
+    pub roles: Vec<Role>,
+    scorer: fn(&WorldState) -> f64,
+}
+
+impl Play {
+    pub fn new(name: &'static str, roles: Vec<Role>, scorer: fn(&WorldState) -> f64) -> Self {
+        Self {
+            name,
+            roles,
+            scorer,
+        }
+    }
+
+    pub fn evaluate(&self, world: &WorldState) -> f64 {
+        (self.scorer)(world)
+    }
+}
+
+/// Assigns available robots to a play's roles by greedy highest-suitability matching; the
+/// result does not necessarily maximize total suitability.
+pub struct Dealer;
+
+impl Dealer {
+    /// Greedily assign robots to roles by repeatedly picking the highest-scoring
+    /// (role, robot) pair among those not yet assigned. `suitability(role, robot)` scores a
+    /// candidate robot for a role, e.g. by distance or readiness. Returns one slot per role,
+    /// `None` where no robot remained to assign.
+    pub fn assign(
+        role_count: usize,
+        robot_count: usize,
+        suitability: impl Fn(usize, usize) -> f64,
+    ) -> Vec<Option<usize>> {
+        let mut assignment = vec![None; role_count];
+        let mut used_robots = vec![false; robot_count];
+
+        for _ in 0..role_count.min(robot_count) {
+            let mut best: Option<(usize, usize, f64)> = None;
+
+            for (role, role_slot) in assignment.iter().enumerate() {
+                if role_slot.is_some() {
+                    continue;
+                }
+                for (robot, &is_used) in used_robots.iter().enumerate() {
+                    if is_used {
+                        continue;
+                    }
+                    let score = suitability(role, robot);
+                    if best.is_none_or(|(_, _, best_score)| score > best_score) {
+                        best = Some((role, robot, score));
+                    }
+                }
+            }
+
+            let Some((role, robot, _)) = best else {
+                break;
+            };
+            assignment[role] = Some(robot);
+            used_robots[robot] = true;
+        }
+
+        assignment
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world_with_robots(robot_positions: Vec<Vector2<f64>>) -> WorldState {
+        WorldState {
+            ball_position: Vector2::new(5.0, 0.0),
+            robot_positions,
+            goal_position: Vector2::new(10.0, 0.0),
+        }
+    }
+
+    #[test]
+    fn role_advances_through_its_skills_in_order() {
+        let mut role = Role::new(vec![
+            Box::new(MoveTo::new(Vector2::new(5.0, 0.0))),
+            Box::new(Shoot::new()),
+        ]);
+        let world = world_with_robots(vec![Vector2::new(5.0, 0.0)]);
+
+        let command = role.command(0, &world).expect("role should still have a skill to run");
+        assert_eq!(command.target, world.goal_position);
+        assert!(!role.is_complete());
+
+        assert!(role.command(0, &world).is_none());
+        assert!(role.is_complete());
+    }
+
+    #[test]
+    fn dealer_assigns_each_role_its_closest_robot() {
+        let robots: [Vector2<f64>; 2] = [Vector2::new(0.0, 0.0), Vector2::new(10.0, 0.0)];
+        let roles: [Vector2<f64>; 2] = [Vector2::new(1.0, 0.0), Vector2::new(9.0, 0.0)];
+
+        let assignment = Dealer::assign(roles.len(), robots.len(), |role: usize, robot: usize| {
+            -(roles[role] - robots[robot]).norm()
+        });
+
+        assert_eq!(assignment, vec![Some(0), Some(1)]);
+    }
+}